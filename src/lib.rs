@@ -18,6 +18,16 @@
 //! - combinations of a particular length
 //! - positions of combinations of a particular length
 //! - positions of qualifying combinations of a particular length
+//! - lazy iteration over combinations via the `Combinations` adaptor
+//! - direct unranking/ranking of a combination via `nth_combination` and
+//!   `combination_rank`
+//! - uniform random sampling of combinations via the `rand` feature
+//! - lazy powerset iteration in increasing cardinality order via `Powerset`
+//! - stack-allocated fixed-size combinations via `array_combinations`
+//! - index-based backtracking combinations via `combinations_backtracking`,
+//!   for sets larger than the 128-element bitmask ceiling
+//! - exhaustive property checking of every subset via `check_all` and
+//!   `count_qualifying`
 //!
 //! *NOTE: positions is the position in the vector of vectors of all combinations
 //! possible, where the list is 1 indexed rather than 0. Therefore if you have
@@ -73,39 +83,60 @@ pub fn get_subset<T: Copy>(v: &Vec<T>, pos: u128) -> Vec<T> {
     subset
 }
 
-/// all gets every combination subset that is possible for the set v
-pub fn all<T: Copy>(v: &Vec<T>) -> Vec<Vec<T>> {
-    let mut subsets: Vec<Vec<T>> = vec![];
-
-    let u_one: u128 = 1;
+/// Combinations is a lazy iterator over every non-empty subset of a vec, yielded in
+/// the same order as the `all` function, but one subset at a time instead
+/// of all at once. This lets callers `.take(n)`, `.filter(...)`, or
+/// short-circuit without paying for the full 2^n enumeration up front.
+pub struct Combinations<T> {
+    v: Vec<T>,
+    pos: u128,
+    end: u128,
+}
 
-    for pos in 1..(u_one << v.len()) {
-        subsets.push(get_subset(&v, pos));
+impl<T: Copy> Combinations<T> {
+    /// new creates a lazy iterator over every non-empty subset of v
+    pub fn new(v: &Vec<T>) -> Self {
+        let u_one: u128 = 1;
+        Combinations {
+            v: v.clone(),
+            pos: 1,
+            end: u_one << v.len(),
+        }
     }
-    subsets
 }
 
-/// all_qualifying gets the combination subsets that qualify according to the
-/// criteria defined in the qualifies callback function
-pub fn all_qualifying<T: Copy>(v: &Vec<T>, qualifies: fn(&Vec<T>) -> bool) -> Vec<Vec<T>> {
-    let mut subsets: Vec<Vec<T>> = vec![];
+impl<T: Copy> Iterator for Combinations<T> {
+    type Item = Vec<T>;
 
-    let u_one: u128 = 1;
-
-    for pos in 1..(u_one << v.len()) {
-        let subset = get_subset(&v, pos);
-        if qualifies(&subset) {
-            subsets.push(subset);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.end {
+            return None;
         }
+        let subset = get_subset(&self.v, self.pos);
+        self.pos += 1;
+        Some(subset)
     }
-    subsets
+}
+
+/// all gets every combination subset that is possible for the set v
+pub fn all<T: Copy>(v: &Vec<T>) -> Vec<Vec<T>> {
+    Combinations::new(v).collect()
+}
+
+/// all_qualifying gets the combination subsets that qualify according to the
+/// criteria defined in the qualifies callback function
+pub fn all_qualifying<T: Copy>(v: &Vec<T>, mut qualifies: impl FnMut(&[T]) -> bool) -> Vec<Vec<T>> {
+    Combinations::new(v).filter(|subset| qualifies(subset)).collect()
 }
 
 /// all_qualifying_positions gets the positions of the combination subsets that
 /// would be generated on the nth iteration from the all function if that
 /// position's subset qualifies according to the criteria defined in the
 /// qualifies callback function
-pub fn all_qualifying_positions<T: Copy>(v: &Vec<T>, qualifies: fn(&Vec<T>) -> bool) -> Vec<u128> {
+pub fn all_qualifying_positions<T: Copy>(
+    v: &Vec<T>,
+    mut qualifies: impl FnMut(&[T]) -> bool,
+) -> Vec<u128> {
     let mut positions: Vec<u128> = vec![];
 
     let u_one: u128 = 1;
@@ -160,7 +191,7 @@ pub fn combinations_positions<T: Copy>(v: &Vec<T>, r: u32) -> Vec<u128> {
 pub fn combinations_qualifying_positions<T: Copy>(
     v: &Vec<T>,
     r: u32,
-    qualifies: fn(&Vec<T>) -> bool,
+    mut qualifies: impl FnMut(&[T]) -> bool,
 ) -> Vec<u128> {
     let mut positions: Vec<u128> = vec![];
 
@@ -178,6 +209,281 @@ pub fn combinations_qualifying_positions<T: Copy>(
     positions
 }
 
+/// binomial computes C(n, k), the number of r-combinations of n items,
+/// without enumerating any of them
+fn binomial(n: u128, k: u128) -> u128 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        result = result * (n - i) / (i + 1);
+    }
+    result
+}
+
+/// nth_combination builds the k-th r-combination of v directly, using the
+/// combinatorial number system, without enumerating any of its
+/// predecessors. k is 0-indexed and must be in 0..C(v.len(), r). Returns an
+/// empty Vec if r is larger than v.len(), since no such combination exists
+pub fn nth_combination<T: Copy>(v: &Vec<T>, r: u32, k: u128) -> Vec<T> {
+    if r as usize > v.len() {
+        return vec![];
+    }
+
+    let n = v.len() as u128;
+    let mut remaining = k;
+    let mut indices: Vec<u128> = Vec::with_capacity(r as usize);
+
+    let mut c = n;
+    for j in (1..=r as u128).rev() {
+        c -= 1;
+        while binomial(c, j) > remaining {
+            c -= 1;
+        }
+        remaining -= binomial(c, j);
+        indices.push(c);
+    }
+
+    indices.reverse();
+    indices.into_iter().map(|i| v[i as usize]).collect()
+}
+
+/// combination_rank is the inverse of nth_combination: given the ascending
+/// indices chosen for a combination, it returns that combination's rank k
+/// such that nth_combination(v, chosen.len() as u32, k) selects the same
+/// indices
+pub fn combination_rank(chosen: &[usize]) -> u128 {
+    chosen
+        .iter()
+        .enumerate()
+        .map(|(i, &index)| binomial(index as u128, (i + 1) as u128))
+        .sum()
+}
+
+/// sample_combination draws a single r-subset of v uniformly at random,
+/// using Floyd's algorithm so that no full enumeration of the combination
+/// space is needed. Returns an empty Vec if r is larger than v.len(), since
+/// no such combination exists
+#[cfg(feature = "rand")]
+pub fn sample_combination<T: Copy, R: rand::Rng>(v: &Vec<T>, r: usize, rng: &mut R) -> Vec<T> {
+    if r > v.len() {
+        return vec![];
+    }
+
+    let n = v.len();
+    let mut selected: std::collections::HashSet<usize> = std::collections::HashSet::with_capacity(r);
+
+    for j in (n - r)..n {
+        let t = rng.gen_range(0..=j);
+        if selected.contains(&t) {
+            selected.insert(j);
+        } else {
+            selected.insert(t);
+        }
+    }
+
+    let mut indices: Vec<usize> = selected.into_iter().collect();
+    indices.sort_unstable();
+    indices.into_iter().map(|i| v[i]).collect()
+}
+
+/// sample_combinations draws count r-subsets of v uniformly at random,
+/// with replacement, by repeatedly calling sample_combination
+#[cfg(feature = "rand")]
+pub fn sample_combinations<T: Copy, R: rand::Rng>(
+    v: &Vec<T>,
+    r: usize,
+    count: usize,
+    rng: &mut R,
+) -> Vec<Vec<T>> {
+    (0..count).map(|_| sample_combination(v, r, rng)).collect()
+}
+
+/// advance_combination moves indices to the next combination of the same
+/// size in lexicographic order, where indices are chosen from 0..n.
+/// Returns false once the last combination of that size has been reached.
+fn advance_combination(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    if k == 0 {
+        return false;
+    }
+
+    let mut i = k;
+    loop {
+        if i == 0 {
+            return false;
+        }
+        i -= 1;
+        if indices[i] != i + n - k {
+            break;
+        }
+    }
+
+    indices[i] += 1;
+    for j in (i + 1)..k {
+        indices[j] = indices[j - 1] + 1;
+    }
+    true
+}
+
+/// Powerset is a lazy iterator over the subsets of v in increasing
+/// cardinality order (all 0-subsets, then all 1-subsets, and so on),
+/// optionally bounded to a min_len..=max_len range of subset sizes so
+/// callers can stream just a slice of the powerset without touching the
+/// rest of the space.
+pub struct Powerset<T> {
+    v: Vec<T>,
+    k: usize,
+    max_len: usize,
+    indices: Option<Vec<usize>>,
+}
+
+impl<T: Copy> Powerset<T> {
+    /// min_len sets the smallest subset size that will be yielded
+    pub fn min_len(mut self, min_len: usize) -> Self {
+        self.k = min_len;
+        self.indices = None;
+        self
+    }
+
+    /// max_len sets the largest subset size that will be yielded
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+}
+
+impl<T: Copy> Iterator for Powerset<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.v.len();
+        if self.k > self.max_len || self.k > n {
+            return None;
+        }
+
+        let indices = self.indices.get_or_insert_with(|| (0..self.k).collect());
+        let subset: Vec<T> = indices.iter().map(|&i| self.v[i]).collect();
+
+        if !advance_combination(indices, n) {
+            self.k += 1;
+            self.indices = None;
+        }
+        Some(subset)
+    }
+}
+
+/// powerset creates a lazy iterator over every subset of v, smallest
+/// cardinality first, unbounded until min_len/max_len narrow the range
+pub fn powerset<T: Copy>(v: &Vec<T>) -> Powerset<T> {
+    Powerset {
+        v: v.clone(),
+        k: 0,
+        max_len: v.len(),
+        indices: None,
+    }
+}
+
+/// ArrayCombinations is a lazy iterator over every K-combination of v,
+/// yielding stack-allocated `[T; K]` arrays instead of heap-allocated
+/// `Vec<T>`s, built with the same lexicographic index advance used by
+/// Powerset.
+pub struct ArrayCombinations<T, const K: usize> {
+    v: Vec<T>,
+    indices: Option<[usize; K]>,
+    done: bool,
+}
+
+impl<T: Copy, const K: usize> Iterator for ArrayCombinations<T, K> {
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || K > self.v.len() {
+            return None;
+        }
+
+        let indices = self.indices.get_or_insert_with(|| core::array::from_fn(|i| i));
+        let out: [T; K] = core::array::from_fn(|i| self.v[indices[i]]);
+
+        if !advance_combination(indices, self.v.len()) {
+            self.done = true;
+        }
+        Some(out)
+    }
+}
+
+/// array_combinations creates a lazy iterator over every K-combination of
+/// v, selected via the standard lexicographic index advance, with no inner
+/// Vec allocation per subset and the combination length enforced by the
+/// type system at the call site
+pub fn array_combinations<T: Copy, const K: usize>(v: &Vec<T>) -> ArrayCombinations<T, K> {
+    ArrayCombinations {
+        v: v.clone(),
+        indices: None,
+        done: false,
+    }
+}
+
+/// combinations_backtracking gets the combination subsets that are possible
+/// for the set v that are the length of the sample size r, the same subsets
+/// as combinations, but without ever building a 2^n bitmask. Subsets come in
+/// lexicographic index order rather than the bitmask order of combinations,
+/// and r = 0 yields one empty subset where combinations yields none. This supports
+/// v.len() >= 128, where the bit-shift used by combinations silently
+/// breaks, bounded only by the size of C(n, r) in the output.
+pub fn combinations_backtracking<T: Copy>(v: &Vec<T>, r: usize) -> Vec<Vec<T>> {
+    let mut subsets: Vec<Vec<T>> = vec![];
+    if r > v.len() {
+        return subsets;
+    }
+
+    let mut current: Vec<usize> = vec![0; r];
+    backtrack(v, r, 0, 0, &mut current, &mut subsets);
+    subsets
+}
+
+/// backtrack recursively fills current with the indices of one combination
+/// per call once index reaches r, pushing the mapped subset into subsets
+fn backtrack<T: Copy>(
+    v: &Vec<T>,
+    r: usize,
+    start: usize,
+    index: usize,
+    current: &mut Vec<usize>,
+    subsets: &mut Vec<Vec<T>>,
+) {
+    if index == r {
+        subsets.push(current.iter().map(|&i| v[i]).collect());
+        return;
+    }
+
+    for c in start..=(v.len() - r + index) {
+        current[index] = c;
+        backtrack(v, r, c + 1, index + 1, current, subsets);
+    }
+}
+
+/// check_all walks every non-empty subset of v, lazily, and returns the first
+/// one for which predicate returns false. Returns Ok(()) if every such subset satisfies
+/// it. predicate is an FnMut so it can fold over state (accumulators, rng
+/// seeds) across the whole combinatorial space.
+pub fn check_all<T: Copy>(v: &Vec<T>, mut predicate: impl FnMut(&[T]) -> bool) -> Result<(), Vec<T>> {
+    for subset in Combinations::new(v) {
+        if !predicate(&subset) {
+            return Err(subset);
+        }
+    }
+    Ok(())
+}
+
+/// count_qualifying tallies how many non-empty subsets of v satisfy predicate,
+/// without building a Vec of the qualifying subsets
+pub fn count_qualifying<T: Copy>(v: &Vec<T>, mut predicate: impl FnMut(&[T]) -> bool) -> usize {
+    Combinations::new(v).filter(|subset| predicate(subset)).count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,6 +496,29 @@ mod tests {
         assert_eq!(result, vec![2, 3]);
     }
 
+    #[test]
+    fn test_combinations_iterator() {
+        let result: Vec<Vec<i32>> = Combinations::new(&(1..4).collect()).collect();
+        assert_eq!(
+            result,
+            vec![
+                vec![1],
+                vec![2],
+                vec![1, 2],
+                vec![3],
+                vec![1, 3],
+                vec![2, 3],
+                vec![1, 2, 3]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_combinations_iterator_take() {
+        let result: Vec<Vec<i32>> = Combinations::new(&(1..4).collect()).take(2).collect();
+        assert_eq!(result, vec![vec![1], vec![2]]);
+    }
+
     #[test]
     fn test_all() {
         let result = all(&(1..4).collect());
@@ -209,7 +538,7 @@ mod tests {
 
     #[test]
     fn test_all_qualifying() {
-        let result = all_qualifying(&(1..4).collect(), |v: &Vec<i32>| -> bool {
+        let result = all_qualifying(&(1..4).collect(), |v: &[i32]| -> bool {
             let sum: i32 = v.iter().sum();
             sum < 5
         });
@@ -221,7 +550,7 @@ mod tests {
 
     #[test]
     fn test_all_qualifying_positions() {
-        let result = all_qualifying_positions(&(1..4).collect(), |v: &Vec<i32>| -> bool {
+        let result = all_qualifying_positions(&(1..4).collect(), |v: &[i32]| -> bool {
             let sum: i32 = v.iter().sum();
             sum < 5
         });
@@ -247,13 +576,195 @@ mod tests {
     #[test]
     fn test_combinations_qualifying_positions() {
         let result =
-            combinations_qualifying_positions(&(1..4).collect(), 2, |v: &Vec<i32>| -> bool {
+            combinations_qualifying_positions(&(1..4).collect(), 2, |v: &[i32]| -> bool {
                 let sum: i32 = v.iter().sum();
                 sum < 5
             });
         assert_eq!(result.len(), 2);
     }
 
+    #[test]
+    fn test_nth_combination() {
+        let v: Vec<i32> = (1..4).collect();
+        let all_combos = combinations(&v, 2);
+        for (k, expected) in all_combos.iter().enumerate() {
+            assert_eq!(&nth_combination(&v, 2, k as u128), expected);
+        }
+    }
+
+    #[test]
+    fn test_nth_combination_r_too_large() {
+        let result = nth_combination(&(1..4).collect(), 5, 0);
+        assert_eq!(result, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_nth_combination_matches_combinations_order() {
+        let v: Vec<i32> = (1..7).collect();
+        let all_combos = combinations(&v, 3);
+        for (k, expected) in all_combos.iter().enumerate() {
+            assert_eq!(&nth_combination(&v, 3, k as u128), expected);
+        }
+    }
+
+    #[test]
+    fn test_combination_rank() {
+        assert_eq!(combination_rank(&[0, 1]), 0);
+        assert_eq!(combination_rank(&[0, 2]), 1);
+        assert_eq!(combination_rank(&[1, 2]), 2);
+    }
+
+    #[test]
+    fn test_combination_rank_is_inverse_of_nth_combination() {
+        let v: Vec<i32> = (1..7).collect();
+        for k in 0..binomial(6, 3) {
+            let combo = nth_combination(&v, 3, k);
+            let indices: Vec<usize> = combo.iter().map(|&x| (x - 1) as usize).collect();
+            assert_eq!(combination_rank(&indices), k);
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample_combination() {
+        use rand::SeedableRng;
+        let v: Vec<i32> = (1..11).collect();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        for _ in 0..50 {
+            let sample = sample_combination(&v, 4, &mut rng);
+            assert_eq!(sample.len(), 4);
+            let mut sorted = sample.clone();
+            sorted.sort_unstable();
+            sorted.dedup();
+            assert_eq!(sorted.len(), 4);
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample_combination_r_too_large() {
+        use rand::SeedableRng;
+        let v: Vec<i32> = (1..4).collect();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let result = sample_combination(&v, 10, &mut rng);
+        assert_eq!(result, Vec::<i32>::new());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample_combinations() {
+        use rand::SeedableRng;
+        let v: Vec<i32> = (1..11).collect();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let samples = sample_combinations(&v, 3, 10, &mut rng);
+        assert_eq!(samples.len(), 10);
+        assert!(samples.iter().all(|s| s.len() == 3));
+    }
+
+    #[test]
+    fn test_powerset() {
+        let result: Vec<Vec<i32>> = powerset(&(1..4).collect()).collect();
+        assert_eq!(
+            result,
+            vec![
+                vec![],
+                vec![1],
+                vec![2],
+                vec![3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3],
+                vec![1, 2, 3],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_powerset_bounded() {
+        let result: Vec<Vec<i32>> = powerset(&(1..6).collect()).min_len(2).max_len(3).collect();
+        assert!(result.iter().all(|s| s.len() >= 2 && s.len() <= 3));
+        assert_eq!(result.len(), 10 + 10);
+    }
+
+    #[test]
+    fn test_array_combinations() {
+        let v: Vec<i32> = (1..5).collect();
+        let result: Vec<[i32; 2]> = array_combinations(&v).collect();
+        assert_eq!(
+            result,
+            vec![[1, 2], [1, 3], [1, 4], [2, 3], [2, 4], [3, 4]]
+        );
+    }
+
+    #[test]
+    fn test_array_combinations_matches_combinations() {
+        // array_combinations walks indices in lexicographic order while
+        // combinations enumerates in bitmask-increasing (colex) order, so
+        // compare the two as sets of subsets rather than as ordered Vecs.
+        use std::collections::BTreeSet;
+        let v: Vec<i32> = (1..7).collect();
+        let expected: BTreeSet<Vec<i32>> = combinations(&v, 3).into_iter().collect();
+        let result: BTreeSet<Vec<i32>> = array_combinations::<i32, 3>(&v).map(Vec::from).collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_combinations_backtracking() {
+        let result = combinations_backtracking(&(1..4).collect(), 2);
+        assert_eq!(result, vec![vec![1, 2], vec![1, 3], vec![2, 3]]);
+    }
+
+    #[test]
+    fn test_combinations_backtracking_matches_combinations() {
+        // combinations_backtracking walks indices in lexicographic order
+        // while combinations enumerates in bitmask-increasing (colex)
+        // order, so compare as sets of subsets rather than as ordered Vecs.
+        use std::collections::BTreeSet;
+        let v: Vec<i32> = (1..10).collect();
+        for r in 1..=v.len() {
+            let expected: BTreeSet<Vec<i32>> = combinations(&v, r as u32).into_iter().collect();
+            let result: BTreeSet<Vec<i32>> = combinations_backtracking(&v, r).into_iter().collect();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_combinations_backtracking_r_too_large() {
+        let result = combinations_backtracking(&(1..4).collect(), 5);
+        assert_eq!(result, Vec::<Vec<i32>>::new());
+    }
+
+    #[test]
+    fn test_check_all_ok() {
+        let result = check_all(&(1..4).collect(), |v: &[i32]| -> bool { v.iter().sum::<i32>() <= 6 });
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_check_all_counterexample() {
+        let result = check_all(&(1..4).collect(), |v: &[i32]| -> bool { v.iter().sum::<i32>() < 5 });
+        assert_eq!(result, Err(vec![2, 3]));
+    }
+
+    #[test]
+    fn test_check_all_closure_captures_state() {
+        let mut seen = 0;
+        let result = check_all(&(1..4).collect(), |_: &[i32]| -> bool {
+            seen += 1;
+            true
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(seen, 7);
+    }
+
+    #[test]
+    fn test_count_qualifying() {
+        let count = count_qualifying(&(1..4).collect(), |v: &[i32]| -> bool {
+            v.iter().sum::<i32>() < 5
+        });
+        assert_eq!(count, 5);
+    }
+
     #[test]
     fn test_complicated() {
         let actual = combinations(&vec![1, 2, 2, 3], 3);